@@ -3,7 +3,9 @@
 
 #[ink::contract]
 mod erc20 {
+    use ink::env::hash::{HashOutput, Keccak256};
     use ink::storage::Mapping;
+    use scale::Encode;
     use trait_erc20::{TERC20, Result, Error};
 
     #[ink(storage)]
@@ -12,6 +14,13 @@ mod erc20 {
         total_supply: Balance,
         balances: Mapping<AccountId, Balance>,
         allowances: Mapping<(AccountId, AccountId), Balance>,
+        locked: Mapping<AccountId, Balance>,
+        lock_time: Mapping<AccountId, Timestamp>,
+        /// Compressed secp256k1 pubkey of the off-chain bridge authority that
+        /// signs mint receipts for tokens backed on the counterpart chain.
+        bridge_authority: [u8; 33],
+        used_nonces: Mapping<u128, ()>,
+        owner: AccountId,
     }
 
     #[ink(event)]
@@ -32,14 +41,39 @@ mod erc20 {
         value: Balance,
     }
 
+    #[ink(event)]
+    pub struct Lock {
+        #[ink(topic)]
+        from: AccountId,
+        amount: Balance,
+        unlock_time: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct Burn {
+        #[ink(topic)]
+        from: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
     impl Erc20 {
         #[ink(constructor)]
-        pub fn new(total_supply: Balance) -> Self {
+        pub fn new(total_supply: Balance, bridge_authority: [u8; 33]) -> Self {
             let mut balances = Mapping::new();
             balances.insert(Self::env().caller(), &total_supply);
             Self {
                 total_supply,
                 balances,
+                bridge_authority,
+                owner: Self::env().caller(),
                 ..Default::default()
             }
         }
@@ -54,8 +88,11 @@ mod erc20 {
                 return Err(Error::BalanceTooLow);
             }
 
-            self.balances.insert(from, &(balance_from - value));
-            self.balances.insert(to, &(balance_to + value));
+            let new_balance_from = balance_from.checked_sub(value).ok_or(Error::Overflow)?;
+            let new_balance_to = balance_to.checked_add(value).ok_or(Error::Overflow)?;
+
+            self.balances.insert(from, &new_balance_from);
+            self.balances.insert(to, &new_balance_to);
 
             self.env().emit_event(Transfer {
                 from: Some(*from),
@@ -65,6 +102,209 @@ mod erc20 {
 
             Ok(())
         }
+
+        /// Locks `amount` of the caller's balance for `duration` (in milliseconds),
+        /// enabling staking/vesting style use cases on top of the plain token.
+        #[ink(message)]
+        pub fn lock(&mut self, amount: Balance, duration: Timestamp) -> Result<()> {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller);
+
+            if balance < amount {
+                return Err(Error::BalanceTooLow);
+            }
+
+            let new_balance = balance.checked_sub(amount).ok_or(Error::Overflow)?;
+            let locked = self.locked_of(caller).checked_add(amount).ok_or(Error::Overflow)?;
+            self.balances.insert(caller, &new_balance);
+            self.locked.insert(caller, &locked);
+
+            let new_unlock_time = self.env().block_timestamp().checked_add(duration).ok_or(Error::Overflow)?;
+            let existing_unlock_time = self.lock_time.get(caller).unwrap_or_default();
+            let unlock_time = core::cmp::max(existing_unlock_time, new_unlock_time);
+            self.lock_time.insert(caller, &unlock_time);
+
+            self.env().emit_event(Lock {
+                from: caller,
+                amount,
+                unlock_time,
+            });
+
+            Ok(())
+        }
+
+        /// Releases the caller's locked balance back into `balances` once the
+        /// recorded unlock time has passed.
+        #[ink(message)]
+        pub fn unlock(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let unlock_time = self.lock_time.get(caller).unwrap_or_default();
+
+            if self.env().block_timestamp() < unlock_time {
+                return Err(Error::StillLocked);
+            }
+
+            let locked = self.locked_of(caller);
+            let balance = self.balance_of(caller);
+            let new_balance = balance.checked_add(locked).ok_or(Error::Overflow)?;
+            self.balances.insert(caller, &new_balance);
+
+            self.locked.remove(caller);
+            self.lock_time.remove(caller);
+
+            Ok(())
+        }
+
+        /// Returns the amount of `owner`'s balance currently locked.
+        #[ink(message)]
+        pub fn locked_of(&self, owner: AccountId) -> Balance {
+            self.locked.get(owner).unwrap_or_default()
+        }
+
+        /// Mints `value` to `to` on the strength of an off-chain receipt signed by
+        /// `bridge_authority`, backing tokens locked/burned on the counterpart chain.
+        /// The signed payload is bound to this contract's `account_id` so a receipt
+        /// cannot be replayed against a different deployment sharing the same
+        /// `bridge_authority`. Each `nonce` can only be redeemed once to prevent
+        /// receipt replay.
+        #[ink(message)]
+        pub fn mint_with_receipt(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            nonce: u128,
+            signature: [u8; 65],
+        ) -> Result<()> {
+            if self.used_nonces.contains(nonce) {
+                return Err(Error::ReceiptAlreadyUsed);
+            }
+
+            let message = (self.env().account_id(), to, value, nonce).encode();
+            let mut hash = <Keccak256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Keccak256>(&message, &mut hash);
+
+            let mut recovered_pubkey = [0u8; 33];
+            self.env()
+                .ecdsa_recover(&signature, &hash, &mut recovered_pubkey)
+                .map_err(|_| Error::InvalidSignature)?;
+
+            if recovered_pubkey != self.bridge_authority {
+                return Err(Error::InvalidSignature);
+            }
+
+            self.used_nonces.insert(nonce, &());
+
+            let balance = self.balance_of(to);
+            let new_balance = balance.checked_add(value).ok_or(Error::Overflow)?;
+            let new_total_supply = self.total_supply.checked_add(value).ok_or(Error::Overflow)?;
+
+            self.balances.insert(to, &new_balance);
+            self.total_supply = new_total_supply;
+
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Burns `value` from the caller's balance so the counterpart chain can
+        /// release the matching amount.
+        #[ink(message)]
+        pub fn burn(&mut self, value: Balance) -> Result<()> {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller);
+
+            if balance < value {
+                return Err(Error::BalanceTooLow);
+            }
+
+            let new_balance = balance.checked_sub(value).ok_or(Error::Overflow)?;
+            let new_total_supply = self.total_supply.checked_sub(value).ok_or(Error::Overflow)?;
+
+            self.balances.insert(caller, &new_balance);
+            self.total_supply = new_total_supply;
+
+            self.env().emit_event(Burn {
+                from: caller,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Swaps the contract's executable code for the code stored under
+        /// `code_hash`, preserving the existing `balances`/`allowances`/
+        /// `total_supply` storage layout so deployers can ship bug fixes
+        /// without migrating balances.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: [u8; 32]) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            self.env()
+                .set_code_hash(&Hash::from(code_hash))
+                .unwrap_or_else(|err| panic!("failed to set code hash: {:?}", err));
+
+            Ok(())
+        }
+
+        /// Transfers ownership of the contract, gating who may call `set_code`.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            self.owner = new_owner;
+
+            self.env().emit_event(OwnershipTransferred {
+                previous_owner: caller,
+                new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Increases the allowance granted to `spender` by `delta`, avoiding the
+        /// approve front-running race of setting the allowance outright.
+        #[ink(message)]
+        pub fn increase_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            let allowance = self.allowances.get(&(owner, spender)).unwrap_or_default();
+            let new_allowance = allowance.checked_add(delta).ok_or(Error::Overflow)?;
+            self.allowances.insert(&(owner, spender), &new_allowance);
+
+            self.env().emit_event(Approval {
+                from: Some(owner),
+                to: Some(spender),
+                value: new_allowance,
+            });
+
+            Ok(())
+        }
+
+        /// Decreases the allowance granted to `spender` by `delta`, avoiding the
+        /// approve front-running race of setting the allowance outright.
+        #[ink(message)]
+        pub fn decrease_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            let allowance = self.allowances.get(&(owner, spender)).unwrap_or_default();
+            let new_allowance = allowance.checked_sub(delta).ok_or(Error::AllowanceUnderflow)?;
+            self.allowances.insert(&(owner, spender), &new_allowance);
+
+            self.env().emit_event(Approval {
+                from: Some(owner),
+                to: Some(spender),
+                value: new_allowance,
+            });
+
+            Ok(())
+        }
     }
 
     impl TERC20 for Erc20 {
@@ -95,6 +335,11 @@ mod erc20 {
             return self.transfer_helper(&from, &to, value)
         }
 
+        /// Sets the allowance to exactly `value`, overwriting whatever was there
+        /// before. This is susceptible to the classic front-running double-spend
+        /// (a spender can use the old allowance and the new one); prefer
+        /// `increase_allowance`/`decrease_allowance` to adjust an existing
+        /// allowance safely.
         #[ink(message)]
         fn approve(&mut self, to: AccountId, value: Balance) -> Result<()> {
             let sender = self.env().caller();
@@ -123,7 +368,7 @@ mod erc20 {
         /// We test if the default constructor does its job.
         #[ink::test]
         fn constructor_works() {
-            let mut erc20 = Erc20::new(1000);
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             // transfer event
             let _ = erc20.transfer(accounts.bob, 1000);
@@ -146,7 +391,7 @@ mod erc20 {
 
         #[ink::test]
         fn transfer_should_work() {
-            let mut erc20 = Erc20::new(1000);
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let res = erc20.transfer(accounts.bob, 100);
 
@@ -157,7 +402,7 @@ mod erc20 {
 
         #[ink::test]
         fn transfer_should_fail() {
-            let mut erc20 = Erc20::new(1000);
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let res = erc20.transfer(accounts.bob, 1001);
 
@@ -165,6 +410,271 @@ mod erc20 {
             assert_eq!(erc20.balance_of(accounts.alice), 1000);
             assert_eq!(erc20.balance_of(accounts.bob), 0);
         }
+
+        #[ink::test]
+        fn lock_should_work() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let res = erc20.lock(400, 1000);
+
+            assert_eq!(res, Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 600);
+            assert_eq!(erc20.locked_of(accounts.alice), 400);
+        }
+
+        #[ink::test]
+        fn unlock_should_fail_before_deadline() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _ = erc20.lock(400, 1000);
+
+            let res = erc20.unlock();
+
+            assert_eq!(res, Err(Error::StillLocked));
+            assert_eq!(erc20.balance_of(accounts.alice), 600);
+            assert_eq!(erc20.locked_of(accounts.alice), 400);
+        }
+
+        #[ink::test]
+        fn unlock_should_work_after_deadline() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _ = erc20.lock(400, 1000);
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            let now = ink::env::block_timestamp::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now + 1000);
+
+            let res = erc20.unlock();
+
+            assert_eq!(res, Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.locked_of(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn repeated_lock_should_not_shorten_deadline() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let _ = erc20.lock(100, 1_000_000);
+            let res = erc20.lock(50, 1);
+
+            assert_eq!(res, Ok(()));
+            assert_eq!(erc20.locked_of(accounts.alice), 150);
+
+            let now = ink::env::block_timestamp::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now + 1);
+
+            assert_eq!(erc20.unlock(), Err(Error::StillLocked));
+        }
+
+        #[ink::test]
+        fn lock_should_reject_overflowing_duration() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1);
+
+            let res = erc20.lock(1, Timestamp::MAX);
+
+            assert_eq!(res, Err(Error::Overflow));
+        }
+
+        /// Signs the keccak-256 hash of `message` with `secret_key` and returns
+        /// the 65-byte recoverable signature alongside the compressed pubkey.
+        fn sign(message: &[u8], secret_key: &[u8; 32]) -> ([u8; 65], [u8; 33]) {
+            use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+            let secp = Secp256k1::new();
+            let secret_key = SecretKey::from_slice(secret_key).expect("valid secret key");
+            let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+
+            let mut hash = <Keccak256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Keccak256>(message, &mut hash);
+            let msg = Message::from_digest_slice(&hash).expect("32 byte hash");
+
+            let (recovery_id, signature) = secp.sign_ecdsa_recoverable(&msg, &secret_key).serialize_compact();
+            let mut sig = [0u8; 65];
+            sig[..64].copy_from_slice(&signature);
+            sig[64] = recovery_id.to_i32() as u8;
+
+            (sig, public_key.serialize())
+        }
+
+        #[ink::test]
+        fn mint_with_receipt_should_work() {
+            let secret_key = [7u8; 32];
+            let (_, bridge_authority) = sign(&[], &secret_key);
+            let mut erc20 = Erc20::new(1000, bridge_authority);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+
+            let message = (callee, accounts.bob, 500 as Balance, 1u128).encode();
+            let (signature, _) = sign(&message, &secret_key);
+
+            let res = erc20.mint_with_receipt(accounts.bob, 500, 1, signature);
+
+            assert_eq!(res, Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 500);
+            assert_eq!(erc20.total_supply(), 1500);
+        }
+
+        #[ink::test]
+        fn mint_with_receipt_should_reject_replay() {
+            let secret_key = [7u8; 32];
+            let (_, bridge_authority) = sign(&[], &secret_key);
+            let mut erc20 = Erc20::new(1000, bridge_authority);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+
+            let message = (callee, accounts.bob, 500 as Balance, 1u128).encode();
+            let (signature, _) = sign(&message, &secret_key);
+
+            assert_eq!(erc20.mint_with_receipt(accounts.bob, 500, 1, signature), Ok(()));
+            assert_eq!(
+                erc20.mint_with_receipt(accounts.bob, 500, 1, signature),
+                Err(Error::ReceiptAlreadyUsed)
+            );
+        }
+
+        #[ink::test]
+        fn mint_with_receipt_should_reject_bad_signature() {
+            let (_, bridge_authority) = sign(&[], &[7u8; 32]);
+            let mut erc20 = Erc20::new(1000, bridge_authority);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+
+            let message = (callee, accounts.bob, 500 as Balance, 1u128).encode();
+            let (signature, _) = sign(&message, &[9u8; 32]);
+
+            assert_eq!(
+                erc20.mint_with_receipt(accounts.bob, 500, 1, signature),
+                Err(Error::InvalidSignature)
+            );
+        }
+
+        #[ink::test]
+        fn burn_should_work() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let res = erc20.burn(300);
+
+            assert_eq!(res, Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 700);
+            assert_eq!(erc20.total_supply(), 700);
+        }
+
+        #[ink::test]
+        fn transfer_ownership_should_work() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let res = erc20.transfer_ownership(accounts.bob);
+            assert_eq!(res, Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(erc20.set_code([1u8; 32]), Ok(()));
+        }
+
+        #[ink::test]
+        fn transfer_ownership_should_fail_for_non_owner() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let res = erc20.transfer_ownership(accounts.bob);
+
+            assert_eq!(res, Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn set_code_should_fail_for_non_owner() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let res = erc20.set_code([1u8; 32]);
+
+            assert_eq!(res, Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn increase_allowance_should_work() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _ = erc20.approve(accounts.bob, 100);
+
+            let res = erc20.increase_allowance(accounts.bob, 50);
+
+            assert_eq!(res, Ok(()));
+            assert_eq!(erc20.allowances.get(&(accounts.alice, accounts.bob)), Some(150));
+        }
+
+        #[ink::test]
+        fn decrease_allowance_should_work() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _ = erc20.approve(accounts.bob, 100);
+
+            let res = erc20.decrease_allowance(accounts.bob, 40);
+
+            assert_eq!(res, Ok(()));
+            assert_eq!(erc20.allowances.get(&(accounts.alice, accounts.bob)), Some(60));
+        }
+
+        #[ink::test]
+        fn decrease_allowance_should_reject_underflow() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _ = erc20.approve(accounts.bob, 100);
+
+            let res = erc20.decrease_allowance(accounts.bob, 150);
+
+            assert_eq!(res, Err(Error::AllowanceUnderflow));
+            assert_eq!(erc20.allowances.get(&(accounts.alice, accounts.bob)), Some(100));
+        }
+
+        #[ink::test]
+        fn increase_allowance_should_reject_overflow() {
+            let mut erc20 = Erc20::new(1000, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _ = erc20.approve(accounts.bob, Balance::MAX);
+
+            let res = erc20.increase_allowance(accounts.bob, 1);
+
+            assert_eq!(res, Err(Error::Overflow));
+            assert_eq!(erc20.allowances.get(&(accounts.alice, accounts.bob)), Some(Balance::MAX));
+        }
+
+        #[ink::test]
+        fn transfer_should_reject_balance_overflow() {
+            let mut erc20 = Erc20::new(1, [0u8; 33]);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            erc20.balances.insert(accounts.bob, &Balance::MAX);
+
+            let res = erc20.transfer(accounts.bob, 1);
+
+            assert_eq!(res, Err(Error::Overflow));
+            assert_eq!(erc20.balance_of(accounts.bob), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn mint_with_receipt_should_reject_supply_overflow() {
+            let secret_key = [7u8; 32];
+            let (_, bridge_authority) = sign(&[], &secret_key);
+            let mut erc20 = Erc20::new(1, bridge_authority);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            erc20.total_supply = Balance::MAX;
+
+            let message = (callee, accounts.bob, 1 as Balance, 1u128).encode();
+            let (signature, _) = sign(&message, &secret_key);
+
+            let res = erc20.mint_with_receipt(accounts.bob, 1, 1, signature);
+
+            assert_eq!(res, Err(Error::Overflow));
+        }
     }
 
 
@@ -188,7 +698,7 @@ mod erc20 {
         #[ink_e2e::test]
         async fn e2e_transfer(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
             let total_supply = 1000;
-            let constructor = Erc20Ref::new(total_supply);
+            let constructor = Erc20Ref::new(total_supply, [0u8; 33]);
             let construct_acc_id = client
                 .instantiate("erc20", &ink_e2e::alice(), constructor, 0, None)
                 .await